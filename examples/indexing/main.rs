@@ -1,6 +1,6 @@
 //! Ensures that cells initialised with coordinates are at the correct place in the state vector.
 
-use casim::ca::{idx_to_coord, Neighborhood, Simulation, VON_NEUMAN_NEIGHBORHOOD};
+use casim::ca::{idx_to_coord, von_neuman, Simulation};
 
 /// Create a grid of cells with coordinates and for any given cell test whether the coordinates of
 /// neighbor cells line up with it.
@@ -19,9 +19,9 @@ struct LocatableCell {
     y: i32,
 }
 fn create_ca(width: i32, height: i32) -> Simulation<LocatableCell> {
-    let trans_fn = |cell: &mut LocatableCell, neigh_it: Neighborhood<LocatableCell>| {
+    let trans_fn = |cell: &mut LocatableCell, neighbors: &[&LocatableCell]| {
         let mut found_neighbors: Vec<(i32, i32)> = Vec::new();
-        for n in neigh_it {
+        for n in neighbors {
             if !((cell.x == n.x && (cell.y == n.y - 1 || cell.y == n.y + 1))
                 || (cell.y == n.y && (cell.x == n.x - 1 || cell.x == n.x + 1)))
             {
@@ -39,7 +39,6 @@ fn create_ca(width: i32, height: i32) -> Simulation<LocatableCell> {
     };
 
     let cells = (0..width * height)
-        .into_iter()
         .map(|idx| {
             let coord = idx_to_coord(width as usize, idx as usize);
             LocatableCell {
@@ -49,5 +48,5 @@ fn create_ca(width: i32, height: i32) -> Simulation<LocatableCell> {
         })
         .collect();
 
-    Simulation::from_cells(width, height, trans_fn, VON_NEUMAN_NEIGHBORHOOD, cells)
+    Simulation::from_cells(width, height, trans_fn, von_neuman, cells)
 }
@@ -0,0 +1,154 @@
+//! Module for agent-based models layered on top of a cellular automaton.
+//!
+//! A [`ComplexAutomaton`] couples a [`crate::ca::Simulation`] with a population of [`Agent`]s
+//! that are positioned on its grid. Each tick, every agent senses the cell it occupies and its
+//! neighborhood, then returns an [`Action`] to update its cell, move to an adjacent coordinate, or
+//! do nothing.
+
+use std::collections::HashSet;
+
+use crate::ca::Simulation;
+
+/// An action an [`Agent`] may take in response to a tick.
+pub enum Action<C> {
+    /// Do nothing this tick.
+    Idle,
+    /// Overwrite the cell the agent currently occupies.
+    UpdateCell(C),
+    /// Move by `(dx, dy)` to an adjacent coordinate. Rejected if the target is out of bounds or
+    /// already claimed by another agent this tick, in which case the agent stays put.
+    Move(i32, i32),
+}
+
+/// An agent that lives on the grid of a [`ComplexAutomaton`].
+pub trait Agent<C> {
+    /// Sense the cell the agent occupies and its neighborhood, and decide on an action.
+    fn act(&mut self, cell: &C, neighbors: &[&C]) -> Action<C>;
+}
+
+/// Couples a [`Simulation`] with a population of [`Agent`]s positioned by `(x, y)`, advancing both
+/// in lockstep.
+pub struct ComplexAutomaton<C: Send, A: Agent<C>> {
+    pub simulation: Simulation<C>,
+    agents: Vec<(i32, i32, A)>,
+}
+
+impl<C: Send, A: Agent<C>> ComplexAutomaton<C, A>
+where
+    C: Clone + Default + std::fmt::Debug,
+{
+    pub fn new(simulation: Simulation<C>) -> Self {
+        ComplexAutomaton {
+            simulation,
+            agents: Vec::new(),
+        }
+    }
+
+    /// Place an agent at `(x, y)`.
+    pub fn add_agent(&mut self, x: i32, y: i32, agent: A) {
+        self.agents.push((x, y, agent));
+    }
+
+    /// The current `(x, y)` position and state of every agent, in stable insertion order.
+    pub fn agents(&self) -> &[(i32, i32, A)] {
+        &self.agents
+    }
+
+    /// Advance the underlying cellular automaton and every agent by one tick.
+    pub fn step(&mut self) {
+        self.simulation.step();
+        self.step_agents();
+    }
+
+    /// Let every agent sense its local neighborhood and act, then apply the resulting moves and
+    /// cell diffs.
+    ///
+    /// Agents act in stable insertion order against the grid state from the start of the tick: a
+    /// move is rejected (the agent stays put) if its target is out of bounds or already claimed,
+    /// by an earlier agent this tick or by an agent that hasn't moved away from it yet.
+    fn step_agents(&mut self) {
+        let width = self.simulation.width();
+        let height = self.simulation.height();
+
+        let mut claimed: HashSet<(i32, i32)> =
+            self.agents.iter().map(|(x, y, _)| (*x, *y)).collect();
+        let mut new_positions = Vec::with_capacity(self.agents.len());
+        let mut cell_diffs = Vec::new();
+
+        for (x, y, agent) in &mut self.agents {
+            let cell = self.simulation.cell_at(*x, *y);
+            let neighbors = self.simulation.neighbors_of(*x, *y);
+
+            match agent.act(cell, &neighbors) {
+                Action::Idle => new_positions.push((*x, *y)),
+                Action::UpdateCell(new_cell) => {
+                    cell_diffs.push((*x, *y, new_cell));
+                    new_positions.push((*x, *y));
+                }
+                Action::Move(dx, dy) => {
+                    let target = (*x + dx, *y + dy);
+                    let in_bounds =
+                        target.0 >= 0 && target.0 < width && target.1 >= 0 && target.1 < height;
+                    if in_bounds && !claimed.contains(&target) {
+                        claimed.remove(&(*x, *y));
+                        claimed.insert(target);
+                        new_positions.push(target);
+                    } else {
+                        new_positions.push((*x, *y));
+                    }
+                }
+            }
+        }
+
+        for (agent, position) in self.agents.iter_mut().zip(new_positions) {
+            agent.0 = position.0;
+            agent.1 = position.1;
+        }
+
+        for (x, y, new_cell) in cell_diffs {
+            self.simulation.set_cell(x, y, new_cell);
+        }
+    }
+}
+
+#[cfg(test)]
+enum Wanderer {
+    MoveBy(i32, i32),
+    Paint(i32),
+}
+
+#[cfg(test)]
+impl Agent<i32> for Wanderer {
+    fn act(&mut self, _cell: &i32, _neighbors: &[&i32]) -> Action<i32> {
+        match *self {
+            Wanderer::MoveBy(dx, dy) => Action::Move(dx, dy),
+            Wanderer::Paint(v) => Action::UpdateCell(v),
+        }
+    }
+}
+
+#[test]
+fn test_move_into_already_claimed_cell_is_rejected() {
+    let sim = Simulation::new(3, 1, |_cell: &mut i32, _n: &[&i32]| {}, crate::ca::von_neuman);
+    let mut ca = ComplexAutomaton::new(sim);
+    ca.add_agent(0, 0, Wanderer::MoveBy(1, 0));
+    ca.add_agent(2, 0, Wanderer::MoveBy(-1, 0));
+
+    ca.step();
+
+    // The first agent (stable insertion order) claims (1, 0) before the second agent's move into
+    // that now-claimed cell is evaluated, so the second agent is rejected and stays put.
+    let positions: Vec<(i32, i32)> = ca.agents().iter().map(|(x, y, _)| (*x, *y)).collect();
+    assert_eq!(positions, vec![(1, 0), (2, 0)]);
+}
+
+#[test]
+fn test_update_cell_diff_lands_via_set_cell() {
+    let sim = Simulation::new(3, 1, |_cell: &mut i32, _n: &[&i32]| {}, crate::ca::von_neuman);
+    let mut ca = ComplexAutomaton::new(sim);
+    ca.add_agent(1, 0, Wanderer::Paint(42));
+
+    ca.step();
+
+    assert_eq!(*ca.simulation.cell_at(1, 0), 42);
+}
@@ -1,6 +1,11 @@
 //! Module for cellular automata
 
 use std::mem;
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// C = Cell
 ///     - data type of the cell
@@ -14,9 +19,27 @@ pub struct Simulation<C: Send> {
     width: i32,
     height: i32,
     transition: Box<dyn FnMut(&mut C, &[&C])>,
-    neighborhood: Box<dyn Fn(i32, i32, i32, i32) -> Vec<(i32, i32)>>,
+    neighborhood: Box<dyn Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + Sync>,
+    boundary: Boundary<C>,
     state: Vec<C>,
     buffer: Vec<C>,
+    /// Shared, thread-safe transition used by [`Simulation::step_parallel`]. Only populated by
+    /// [`Simulation::new_parallel`], since an ordinary `FnMut` transition cannot be called
+    /// concurrently from multiple threads.
+    #[cfg(feature = "rayon")]
+    parallel_transition: Option<Arc<dyn Fn(&mut C, &[&C]) + Sync + Send>>,
+}
+
+/// How a [`Simulation`] treats neighbor coordinates that fall outside of `width`/`height`.
+pub enum Boundary<C> {
+    /// The grid is a torus: coordinates wrap around modulo `width`/`height`.
+    Wrap,
+    /// Out-of-range neighbors are simply omitted, so border cells see fewer neighbors. This is
+    /// the historical behavior of [`von_neuman`].
+    Void,
+    /// Out-of-range neighbors are replaced by a constant cell, so every cell always sees a full
+    /// neighborhood.
+    Fixed(C),
 }
 
 /// T applies a function to Cell of buffer 1 and neighborhood and then puts a clone of the cell with the new state in buffer 2
@@ -28,7 +51,7 @@ where
         width: i32,
         height: i32,
         trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
-        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + Sync + 'static,
     ) -> Self {
         let capacity: usize = (width * height) as usize;
         let state = vec![C::default(); capacity];
@@ -39,8 +62,11 @@ where
             height,
             transition: Box::new(trans_fn),
             neighborhood: Box::new(neighbor_fn),
+            boundary: Boundary::Void,
             state,
             buffer,
+            #[cfg(feature = "rayon")]
+            parallel_transition: None,
         }
     }
 
@@ -49,7 +75,7 @@ where
         width: i32,
         height: i32,
         trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
-        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + Sync + 'static,
         cells: Vec<C>,
     ) -> Self {
         Simulation {
@@ -57,30 +83,110 @@ where
             height,
             transition: Box::new(trans_fn),
             neighborhood: Box::new(neighbor_fn),
-            state: cells.to_vec(),
+            boundary: Boundary::Void,
+            state: cells.clone(),
             buffer: cells,
+            #[cfg(feature = "rayon")]
+            parallel_transition: None,
         }
     }
 
+    /// Build a [`Simulation`] whose transition can also be driven in parallel via
+    /// [`Simulation::step_parallel`]. Requires `trans_fn` to be `Fn + Sync + Send` (rather than
+    /// just `FnMut`) so it can safely be shared across worker threads.
+    #[cfg(feature = "rayon")]
+    pub fn new_parallel(
+        width: i32,
+        height: i32,
+        trans_fn: impl Fn(&mut C, &[&C]) + Sync + Send + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + Sync + 'static,
+    ) -> Self {
+        let trans_fn = Arc::new(trans_fn);
+        let trans_fn_for_serial_step = Arc::clone(&trans_fn);
+        let mut sim = Self::new(
+            width,
+            height,
+            move |cell: &mut C, neighbors: &[&C]| trans_fn_for_serial_step(cell, neighbors),
+            neighbor_fn,
+        );
+        sim.parallel_transition = Some(trans_fn);
+        sim
+    }
+
+    /// Set how out-of-range neighbor coordinates are treated. Defaults to [`Boundary::Void`].
+    #[must_use]
+    pub fn with_boundary(mut self, boundary: Boundary<C>) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
     /// Perform one simulation step.
     pub fn step(&mut self) {
         // Manipulate the internal state of a cell the `buffer` grid by iterating over the cells at
         // the neighborhood coordinates in the `state` grid.
         let w = self.width;
+        let h = self.height;
         let buf_ref = &mut self.buffer;
         let state_ref = &self.state;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let neighbors: Vec<&C> = (self.neighborhood)(x, y, self.width, self.height)
+        let boundary = &self.boundary;
+        for y in 0..h {
+            for x in 0..w {
+                let idx = coord_to_idx(w, x, y);
+                let neighbors: Vec<&C> = (self.neighborhood)(x, y, w, h)
                     .iter()
-                    .map(|(i, j)| &state_ref[coord_to_idx(w, *i, *j)])
+                    .filter_map(|(i, j)| resolve_neighbor(state_ref, boundary, w, h, *i, *j))
                     .collect();
-                (self.transition)(&mut buf_ref[coord_to_idx(w, x, y)], &neighbors)
+                // Seed the write-buffer cell with the current state so `transition` sees the
+                // cell's own value, not whatever stale value the buffer still holds.
+                buf_ref[idx].clone_from(&state_ref[idx]);
+                (self.transition)(&mut buf_ref[idx], &neighbors);
             }
         }
 
         // Swap the assignments of `state` and `buffer` to "update the grid", so to speak.
-        mem::swap(&mut self.state, &mut self.buffer)
+        mem::swap(&mut self.state, &mut self.buffer);
+    }
+
+    /// Perform one simulation step, evaluating the transition for every cell in parallel via
+    /// rayon. Only available on a [`Simulation`] constructed with
+    /// [`Simulation::new_parallel`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `Simulation` wasn't built with [`Simulation::new_parallel`].
+    #[cfg(feature = "rayon")]
+    pub fn step_parallel(&mut self)
+    where
+        C: Sync,
+    {
+        let w = self.width;
+        let h = self.height;
+        let state_ref = &self.state;
+        let boundary = &self.boundary;
+        let neighborhood = &self.neighborhood;
+        let transition = self
+            .parallel_transition
+            .as_ref()
+            .expect("step_parallel requires a Simulation built with Simulation::new_parallel");
+
+        self.buffer
+            .par_chunks_mut(w as usize)
+            .enumerate()
+            .for_each(|(y, row)| {
+                for (x, cell) in row.iter_mut().enumerate() {
+                    let neighbors: Vec<&C> = neighborhood(x as i32, y as i32, w, h)
+                        .iter()
+                        .filter_map(|(i, j)| resolve_neighbor(state_ref, boundary, w, h, *i, *j))
+                        .collect();
+                    // Seed the write-buffer cell with the current state, matching `step()`, so
+                    // `transition` sees the cell's own value rather than a stale buffer value.
+                    let idx = coord_to_idx(w, x as i32, y as i32);
+                    cell.clone_from(&state_ref[idx]);
+                    transition(cell, &neighbors);
+                }
+            });
+
+        mem::swap(&mut self.state, &mut self.buffer);
     }
 
     pub fn step_until(&mut self, step_count: i32) {
@@ -92,6 +198,39 @@ where
     pub fn cells(&self) -> &[C] {
         &self.state
     }
+
+    pub const fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub const fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Get the current state of the cell at `(x, y)`.
+    pub fn cell_at(&self, x: i32, y: i32) -> &C {
+        &self.state[coord_to_idx(self.width, x, y)]
+    }
+
+    /// Directly overwrite the cell at `(x, y)`, bypassing `transition`.
+    ///
+    /// Intended for callers outside of a `step()`, such as a [`crate::abm::ComplexAutomaton`]
+    /// applying agent-driven diffs.
+    pub fn set_cell(&mut self, x: i32, y: i32, cell: C) {
+        let idx = coord_to_idx(self.width, x, y);
+        self.state[idx] = cell;
+    }
+
+    /// Get the resolved neighborhood of the cell at `(x, y)`, honoring the configured
+    /// [`Boundary`].
+    pub fn neighbors_of(&self, x: i32, y: i32) -> Vec<&C> {
+        (self.neighborhood)(x, y, self.width, self.height)
+            .iter()
+            .filter_map(|(i, j)| {
+                resolve_neighbor(&self.state, &self.boundary, self.width, self.height, *i, *j)
+            })
+            .collect()
+    }
 }
 
 pub fn coord_to_idx(width: i32, x: i32, y: i32) -> usize {
@@ -104,65 +243,90 @@ pub fn idx_to_coord(width: usize, idx: usize) -> (i32, i32) {
     (x as i32, y as i32)
 }
 
-static VON_NEUMAN_NEIGHBORHOOD: &'static [(i32, i32); 4] = &[(-1, 0), (0, -1), (1, 0), (0, 1)];
+static VON_NEUMAN_NEIGHBORHOOD: &[(i32, i32); 4] = &[(-1, 0), (0, -1), (1, 0), (0, 1)];
 
-struct Neighborhood {
-    count: usize,
-    bounds: &'static [(i32, i32)],
-    ca_bounds: (i32, i32),
-    cell_coords: Option<(i32, i32)>,
+/// Von Neumann neighborhood (the four orthogonal neighbors), expressed via the precomputed
+/// `VON_NEUMAN_NEIGHBORHOOD` deltas so no per-call offset computation is needed.
+///
+/// Coordinates may fall outside of `width`/`height`; it is up to the [`Simulation`]'s [`Boundary`]
+/// to decide how such neighbors are resolved.
+pub fn von_neuman(x: i32, y: i32, _width: i32, _height: i32) -> Vec<(i32, i32)> {
+    VON_NEUMAN_NEIGHBORHOOD
+        .iter()
+        .map(|(a, b)| (x + a, y + b))
+        .collect::<Vec<(i32, i32)>>()
 }
 
-impl Neighborhood {
-    fn new(bounds: &'static [(i32, i32)], ca_bounds: (i32, i32)) -> Self {
-        Neighborhood {
-            count: 0,
-            bounds,
-            ca_bounds,
-            cell_coords: None,
-        }
-    }
-
-    fn init_with_cell(&mut self, cell: (i32, i32)) {
-        self.cell_coords = Some(cell);
-    }
+static MOORE_NEIGHBORHOOD: &[(i32, i32); 8] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
 
-    fn reset(&mut self) {
-        self.count = 0;
-    }
+/// Moore neighborhood (the eight surrounding neighbors), expressed via the precomputed
+/// `MOORE_NEIGHBORHOOD` deltas so no per-call offset computation is needed.
+///
+/// Coordinates may fall outside of `width`/`height`; it is up to the [`Simulation`]'s [`Boundary`]
+/// to decide how such neighbors are resolved.
+pub fn moore(x: i32, y: i32, _width: i32, _height: i32) -> Vec<(i32, i32)> {
+    MOORE_NEIGHBORHOOD
+        .iter()
+        .map(|(a, b)| (x + a, y + b))
+        .collect::<Vec<(i32, i32)>>()
 }
 
-// Implement `Iterator` for `Fibonacci`.
-// The `Iterator` trait only requires a method to be defined for the `next` element.
-impl Iterator for Neighborhood {
-    // We can refer to this type using Self::Item
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.count == self.bounds.len() {
-            None
-        } else {
-            let cell = self.cell_coords.unwrap();
-            let neigh = (
-                self.bounds[self.count].0 + cell.0,
-                self.bounds[self.count].1 + cell.1,
-            );
-            let idx = coord_to_idx(self.ca_bounds.0, cell.0, cell.1);
-            if idx < 0 || idx > self.ca_bounds.0 {
+/// Resolve a (possibly out-of-range) neighbor coordinate to a cell reference according to
+/// `boundary`, returning `None` if the neighbor should be omitted entirely (only happens for
+/// [`Boundary::Void`]).
+fn resolve_neighbor<'a, C>(
+    state: &'a [C],
+    boundary: &'a Boundary<C>,
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+) -> Option<&'a C> {
+    let in_bounds = x >= 0 && x < width && y >= 0 && y < height;
+    match boundary {
+        Boundary::Wrap => {
+            let wx = x.rem_euclid(width);
+            let wy = y.rem_euclid(height);
+            Some(&state[coord_to_idx(width, wx, wy)])
+        }
+        Boundary::Void => {
+            if in_bounds {
+                Some(&state[coord_to_idx(width, x, y)])
+            } else {
                 None
+            }
+        }
+        Boundary::Fixed(fixed) => {
+            if in_bounds {
+                Some(&state[coord_to_idx(width, x, y)])
             } else {
-                Some(idx)
+                Some(fixed)
             }
         }
     }
 }
 
-pub fn von_neuman(x: i32, y: i32, width: i32, height: i32) -> Vec<(i32, i32)> {
-    VON_NEUMAN_NEIGHBORHOOD
-        .iter()
-        .map(|(a, b)| (x + a, y + b))
-        .filter(|(a, b)| *a >= 0 && *a < width && *b >= 0 && *b < height)
-        .collect::<Vec<(i32, i32)>>()
+#[test]
+fn test_wrap_boundary_produces_full_neighborhood() {
+    let mut sim = Simulation::from_cells(
+        3,
+        3,
+        |cell: &mut i32, neighbors: &[&i32]| *cell = neighbors.len() as i32,
+        von_neuman,
+        vec![0; 9],
+    )
+    .with_boundary(Boundary::Wrap);
+    sim.step();
+    assert!(sim.cells().iter().all(|&count| count == 4));
 }
 
 #[test]
@@ -176,6 +340,29 @@ fn test_roundtrip_idx_coords() {
     }
 }
 
+#[test]
+#[cfg(feature = "rayon")]
+fn test_step_parallel_matches_serial_step() {
+    let trans_fn = |cell: &mut i32, neighbors: &[&i32]| {
+        *cell = neighbors.iter().copied().sum::<i32>() % 7;
+    };
+
+    let seed: Vec<i32> = (0..64).collect();
+
+    let mut serial =
+        Simulation::from_cells(8, 8, trans_fn, moore, seed.clone()).with_boundary(Boundary::Wrap);
+    let mut parallel = Simulation::new_parallel(8, 8, trans_fn, moore).with_boundary(Boundary::Wrap);
+    for (idx, cell) in seed.iter().enumerate() {
+        let (x, y) = idx_to_coord(8, idx);
+        parallel.set_cell(x, y, *cell);
+    }
+
+    serial.step();
+    parallel.step_parallel();
+
+    assert_eq!(serial.cells(), parallel.cells());
+}
+
 #[test]
 fn test_roundtrip_coords_idx() {
     for width in 1..49 {
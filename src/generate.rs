@@ -0,0 +1,191 @@
+//! Procedural initial-state generation: noise seeding, cave-style smoothing, and region pruning.
+//!
+//! Produces organic starting grids for a [`Simulation`] instead of requiring the caller to build
+//! `cells` by hand. [`generate`] runs three steps: seed each cell as wall/open from a thresholded
+//! RNG, run `smoothing_passes` of the classic cave-generation smoothing rule over the Moore
+//! neighborhood, then flood-fill to find connected open regions and fill back in every region
+//! smaller than `min_region_size`. Every open region of at least `min_region_size` cells is kept,
+//! so more than one cavern can remain.
+
+use std::collections::VecDeque;
+
+use crate::ca::Simulation;
+
+/// Options controlling [`generate`].
+pub struct GenOptions {
+    /// Seed for the deterministic RNG; the same seed and options always produce the same grid.
+    pub seed: u64,
+    /// Fraction of cells in `[0.0, 1.0]` seeded as wall before smoothing.
+    pub density: f64,
+    /// Number of cave-smoothing passes to run after seeding.
+    pub smoothing_passes: u32,
+    /// Connected open regions smaller than this many cells are filled back in as wall.
+    pub min_region_size: usize,
+}
+
+/// A minimal seedable xorshift64 RNG. Sufficient for deterministic, reproducible seeding without
+/// pulling in an external RNG crate.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        // xorshift is undefined for a zero state, so nudge it to a nonzero one.
+        Xorshift64 {
+            state: seed.max(1),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// A pseudo-random value in `[0.0, 1.0)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+const fn idx(width: i32, x: i32, y: i32) -> usize {
+    (y * width + x) as usize
+}
+
+const MOORE_OFFSETS: [(i32, i32); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+const VON_NEUMAN_OFFSETS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Generate a `width` x `height` grid of `bool`s (`true` = wall) via noise seeding, cave
+/// smoothing, and small-region pruning.
+#[must_use]
+pub fn generate(width: i32, height: i32, opts: &GenOptions) -> Vec<bool> {
+    let mut rng = Xorshift64::new(opts.seed);
+    let mut cells: Vec<bool> = (0..width * height)
+        .map(|_| rng.next_f64() < opts.density)
+        .collect();
+
+    for _ in 0..opts.smoothing_passes {
+        cells = smooth_pass(width, height, &cells);
+    }
+
+    prune_small_regions(width, height, &mut cells, opts.min_region_size);
+
+    cells
+}
+
+/// A cell becomes wall if at least 5 of its 8 Moore neighbors are walls, else open.
+/// Out-of-bounds neighbors count as wall.
+fn smooth_pass(width: i32, height: i32, cells: &[bool]) -> Vec<bool> {
+    (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| {
+                let wall_neighbors = MOORE_OFFSETS
+                    .iter()
+                    .filter(|(dx, dy)| {
+                        let (nx, ny) = (x + dx, y + dy);
+                        nx < 0 || nx >= width || ny < 0 || ny >= height || cells[idx(width, nx, ny)]
+                    })
+                    .count();
+                wall_neighbors >= 5
+            })
+        })
+        .collect()
+}
+
+/// Flood-fill over 4-connected open cells to label connected regions, then turn every region
+/// smaller than `min_region_size` back into wall.
+fn prune_small_regions(width: i32, height: i32, cells: &mut [bool], min_region_size: usize) {
+    let mut visited = vec![false; cells.len()];
+
+    for start in 0..cells.len() {
+        if visited[start] || cells[start] {
+            continue;
+        }
+
+        let mut region = Vec::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        visited[start] = true;
+
+        while let Some(current) = queue.pop_front() {
+            region.push(current);
+            let x = current as i32 % width;
+            let y = current as i32 / width;
+
+            for (dx, dy) in VON_NEUMAN_OFFSETS {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx >= 0 && nx < width && ny >= 0 && ny < height {
+                    let n = idx(width, nx, ny);
+                    if !visited[n] && !cells[n] {
+                        visited[n] = true;
+                        queue.push_back(n);
+                    }
+                }
+            }
+        }
+
+        if region.len() < min_region_size {
+            for cell in region {
+                cells[cell] = true;
+            }
+        }
+    }
+}
+
+impl<C> Simulation<C>
+where
+    C: Clone + Default + std::fmt::Debug + Send,
+{
+    /// Build a [`Simulation`] whose initial `cells` come from [`generate`] instead of being
+    /// filled in by hand. `cell_fmt` maps the generated `true`/`false` (wall/open) grid to `C`.
+    pub fn from_generator(
+        width: i32,
+        height: i32,
+        opts: &GenOptions,
+        trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + Sync + 'static,
+        cell_fmt: impl Fn(bool) -> C,
+    ) -> Self {
+        let cells = generate(width, height, opts)
+            .into_iter()
+            .map(cell_fmt)
+            .collect();
+        Simulation::from_cells(width, height, trans_fn, neighbor_fn, cells)
+    }
+}
+
+#[test]
+fn test_generate_is_deterministic_for_a_given_seed() {
+    let opts = GenOptions {
+        seed: 42,
+        density: 0.45,
+        smoothing_passes: 3,
+        min_region_size: 10,
+    };
+    assert_eq!(generate(40, 40, &opts), generate(40, 40, &opts));
+}
+
+#[test]
+fn test_prune_small_regions_removes_isolated_open_cells() {
+    let width = 3;
+    let height = 3;
+    // A single open cell surrounded by walls, too small to survive a min_region_size of 2.
+    let mut cells = vec![true; 9];
+    cells[idx(width, 1, 1)] = false;
+    prune_small_regions(width, height, &mut cells, 2);
+    assert!(cells.iter().all(|&wall| wall));
+}
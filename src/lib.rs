@@ -23,7 +23,12 @@
     clippy::cast_possible_truncation,
     clippy::cast_possible_wrap,
     clippy::cast_sign_loss,
-    clippy::use_self
+    clippy::use_self,
+    clippy::type_complexity,
+    clippy::type_repetition_in_bounds,
+    clippy::missing_const_for_fn,
+    clippy::must_use_candidate,
+    clippy::too_long_first_doc_paragraph
 )]
 
 #[macro_use]
@@ -32,8 +37,8 @@ extern crate pretty_env_logger;
 
 pub mod abm;
 pub mod ca;
-
-// TODO: Add RNG
-// TODO: Add cell initialisation (method)
-//       - maybe use constructor for use with a `LocatedCell` trait
-// TODO: Add simple terminal-based visualisation
+pub mod generate;
+pub mod nd;
+pub mod render;
+pub mod ruleset;
+pub mod sparse;
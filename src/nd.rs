@@ -0,0 +1,191 @@
+//! N-dimensional generalization of the 2D engine in [`crate::ca`].
+//!
+//! [`SimulationND`] drives the same double-buffered `transition` logic as [`crate::ca::Simulation`],
+//! but over a grid of arbitrary dimensionality `D` (3D and 4D Game-of-Life variants, for example)
+//! by replacing the `(x, y)` coordinate pair with a [`PositionND`] and `width`/`coord_to_idx` with
+//! precomputed strides.
+
+use std::mem;
+
+/// A position in a `D`-dimensional grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PositionND<const D: usize>(pub [i32; D]);
+
+/// Precomputed shape and strides for converting between [`PositionND`] and a flat index, the
+/// N-dimensional analogue of [`crate::ca::coord_to_idx`]/[`crate::ca::idx_to_coord`].
+#[derive(Clone, Copy, Debug)]
+struct Strides<const D: usize> {
+    dims: [i32; D],
+    strides: [i32; D],
+}
+
+impl<const D: usize> Strides<D> {
+    fn new(dims: [i32; D]) -> Self {
+        let mut strides = [1; D];
+        for i in 1..D {
+            strides[i] = strides[i - 1] * dims[i - 1];
+        }
+        Strides { dims, strides }
+    }
+
+    fn len(&self) -> usize {
+        self.dims.iter().product::<i32>() as usize
+    }
+
+    fn in_bounds(&self, pos: PositionND<D>) -> bool {
+        pos.0
+            .iter()
+            .zip(self.dims.iter())
+            .all(|(c, d)| *c >= 0 && c < d)
+    }
+
+    fn coord_to_idx(&self, pos: PositionND<D>) -> usize {
+        pos.0
+            .iter()
+            .zip(self.strides.iter())
+            .map(|(c, s)| c * s)
+            .sum::<i32>() as usize
+    }
+
+    fn idx_to_coord(&self, idx: usize) -> PositionND<D> {
+        let mut coord = [0; D];
+        let mut rem = idx as i32;
+        for i in (0..D).rev() {
+            coord[i] = rem / self.strides[i];
+            rem %= self.strides[i];
+        }
+        PositionND(coord)
+    }
+}
+
+fn add_offset<const D: usize>(pos: [i32; D], offset: [i32; D]) -> [i32; D] {
+    let mut out = [0; D];
+    for i in 0..D {
+        out[i] = pos[i] + offset[i];
+    }
+    out
+}
+
+/// The Moore neighborhood in `D` dimensions: the Cartesian product of `{-1, 0, 1}^D`, minus the
+/// origin.
+#[must_use]
+pub fn moore_neighborhood<const D: usize>() -> Vec<[i32; D]> {
+    let total = 3_usize.pow(D as u32);
+    (0..total)
+        .map(|n| {
+            let mut rem = n;
+            let mut offset = [0i32; D];
+            for o in &mut offset {
+                *o = (rem % 3) as i32 - 1;
+                rem /= 3;
+            }
+            offset
+        })
+        .filter(|offset| offset.iter().any(|&c| c != 0))
+        .collect()
+}
+
+/// The von Neumann neighborhood in `D` dimensions: the unit vectors along each axis, in both
+/// directions.
+#[must_use]
+pub fn von_neumann_neighborhood<const D: usize>() -> Vec<[i32; D]> {
+    let mut result = Vec::with_capacity(2 * D);
+    for axis in 0..D {
+        let mut plus = [0i32; D];
+        plus[axis] = 1;
+        result.push(plus);
+
+        let mut minus = [0i32; D];
+        minus[axis] = -1;
+        result.push(minus);
+    }
+    result
+}
+
+/// The N-dimensional counterpart of [`crate::ca::Simulation`].
+///
+/// Out-of-range neighbors are simply omitted, matching the historical (pre-[`crate::ca::Boundary`])
+/// behavior of the 2D engine.
+pub struct SimulationND<C: Send, const D: usize> {
+    strides: Strides<D>,
+    transition: Box<dyn FnMut(&mut C, &[&C])>,
+    neighborhood: Vec<[i32; D]>,
+    state: Vec<C>,
+    buffer: Vec<C>,
+}
+
+impl<C: Send, const D: usize> SimulationND<C, D>
+where
+    C: Clone + Default + std::fmt::Debug,
+{
+    pub fn new(
+        dims: [i32; D],
+        trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
+        neighborhood: Vec<[i32; D]>,
+    ) -> Self {
+        let strides = Strides::new(dims);
+        let capacity = strides.len();
+        let state = vec![C::default(); capacity];
+        let buffer = vec![C::default(); capacity];
+        debug!("creating {D}-dimensional simulation");
+        SimulationND {
+            strides,
+            transition: Box::new(trans_fn),
+            neighborhood,
+            state,
+            buffer,
+        }
+    }
+
+    /// Perform one simulation step.
+    pub fn step(&mut self) {
+        let strides = self.strides;
+        let neighborhood = &self.neighborhood;
+        let buf_ref = &mut self.buffer;
+        let state_ref = &self.state;
+        for (idx, cell) in buf_ref.iter_mut().enumerate() {
+            let pos = strides.idx_to_coord(idx);
+            let neighbors: Vec<&C> = neighborhood
+                .iter()
+                .map(|offset| PositionND(add_offset(pos.0, *offset)))
+                .filter(|p| strides.in_bounds(*p))
+                .map(|p| &state_ref[strides.coord_to_idx(p)])
+                .collect();
+            // Seed the write-buffer cell with the current state, matching `ca::Simulation::step`,
+            // so `transition` sees the cell's own value rather than a stale buffer value.
+            cell.clone_from(&state_ref[idx]);
+            (self.transition)(cell, &neighbors);
+        }
+
+        mem::swap(&mut self.state, &mut self.buffer);
+    }
+
+    pub fn step_until(&mut self, step_count: i32) {
+        for _ in 0..step_count {
+            self.step();
+        }
+    }
+
+    pub fn cells(&self) -> &[C] {
+        &self.state
+    }
+}
+
+#[test]
+fn test_3d_moore_neighborhood_has_26_offsets() {
+    assert_eq!(moore_neighborhood::<3>().len(), 26);
+}
+
+#[test]
+fn test_4d_von_neumann_neighborhood_has_8_offsets() {
+    assert_eq!(von_neumann_neighborhood::<4>().len(), 8);
+}
+
+#[test]
+fn test_strides_roundtrip() {
+    let strides = Strides::new([4, 5, 3]);
+    for idx in 0..strides.len() {
+        let pos = strides.idx_to_coord(idx);
+        assert_eq!(strides.coord_to_idx(pos), idx);
+    }
+}
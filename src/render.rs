@@ -0,0 +1,74 @@
+//! Terminal visualization of a [`Simulation`]'s grid via raw ANSI escape codes.
+//!
+//! No graphics stack required: [`render`] draws [`Simulation::cells`] to stdout as colored
+//! glyphs, moving the cursor back to the top-left before each frame so successive calls redraw in
+//! place instead of scrolling. [`Simulation::run_tui`] wraps stepping and rendering into a single
+//! out-of-the-box loop.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::ca::Simulation;
+
+/// An ANSI foreground color for a rendered cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    const fn ansi_fg_code(self) -> u8 {
+        match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+}
+
+/// Render a row-major grid of `cells`, `width` cells wide, to stdout. `cell_fmt` maps each cell to
+/// a glyph and an ANSI foreground color.
+pub fn render<C>(cells: &[C], width: i32, cell_fmt: &impl Fn(&C) -> (char, Color)) {
+    let mut frame = String::from("\x1b[H");
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 && i as i32 % width == 0 {
+            frame.push('\n');
+        }
+        let (glyph, color) = cell_fmt(cell);
+        let _ = write!(frame, "\x1b[{}m{glyph}\x1b[0m", color.ansi_fg_code());
+    }
+    frame.push('\n');
+    print!("{frame}");
+    let _ = io::stdout().flush();
+}
+
+impl<C> Simulation<C>
+where
+    C: Clone + Default + std::fmt::Debug + Send,
+{
+    /// Step the simulation `steps` times, rendering a frame with [`render`] before and after each
+    /// step and pausing `frame_delay` between frames.
+    pub fn run_tui(&mut self, steps: u32, frame_delay: Duration, cell_fmt: impl Fn(&C) -> (char, Color)) {
+        print!("\x1b[2J"); // clear the screen once, up front
+        render(self.cells(), self.width(), &cell_fmt);
+        for _ in 0..steps {
+            thread::sleep(frame_delay);
+            self.step();
+            render(self.cells(), self.width(), &cell_fmt);
+        }
+    }
+}
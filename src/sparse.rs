@@ -0,0 +1,155 @@
+//! Sparse, hash-map-backed alternative to the dense [`crate::ca::Simulation`], for automata over
+//! huge domains that spend most of their cells in the default state.
+//!
+//! Rather than a `Vec<C>` of length `width * height`, [`SparseSimulation`] stores only non-default
+//! cells in a `HashMap`, and each [`SparseSimulation::step`] only evaluates `transition` on the
+//! frontier of occupied cells and their neighbors, dropping any cell whose result reverts to
+//! `C::default()`. Coordinates are bounds-free, so the grid can grow unbounded in every direction.
+//! `transition`/`neighborhood` reuse the same shapes as [`crate::ca::Simulation`], so rules are
+//! shared between the two backends.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasherDefault;
+use std::mem;
+
+type Coord = (i32, i32);
+type CoordMap<C> = HashMap<Coord, C, BuildHasherDefault<DefaultHasher>>;
+
+pub struct SparseSimulation<C> {
+    transition: Box<dyn FnMut(&mut C, &[&C])>,
+    neighborhood: Box<dyn Fn(i32, i32, i32, i32) -> Vec<(i32, i32)>>,
+    cells: CoordMap<C>,
+    buffer: CoordMap<C>,
+}
+
+impl<C> SparseSimulation<C>
+where
+    C: Clone + Default + PartialEq + std::fmt::Debug,
+{
+    pub fn new(
+        trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + 'static,
+    ) -> Self {
+        debug!("creating sparse simulation");
+        SparseSimulation {
+            transition: Box::new(trans_fn),
+            neighborhood: Box::new(neighbor_fn),
+            cells: CoordMap::default(),
+            buffer: CoordMap::default(),
+        }
+    }
+
+    /// Build a [`SparseSimulation`] seeded with `cells`. Any cell equal to `C::default()` is
+    /// dropped, since the sparse backend only ever stores non-default cells.
+    pub fn from_cells(
+        trans_fn: impl FnMut(&mut C, &[&C]) + 'static,
+        neighbor_fn: impl Fn(i32, i32, i32, i32) -> Vec<(i32, i32)> + 'static,
+        cells: impl IntoIterator<Item = (i32, i32, C)>,
+    ) -> Self {
+        let mut sim = Self::new(trans_fn, neighbor_fn);
+        for (x, y, cell) in cells {
+            if cell != C::default() {
+                sim.cells.insert((x, y), cell);
+            }
+        }
+        sim
+    }
+
+    /// Perform one simulation step, evaluating `transition` only on the frontier formed by the
+    /// union of all occupied cells and their neighbors.
+    pub fn step(&mut self) {
+        let default = C::default();
+
+        let mut candidates: HashSet<Coord> = HashSet::new();
+        for &(x, y) in self.cells.keys() {
+            candidates.insert((x, y));
+            candidates.extend((self.neighborhood)(x, y, i32::MAX, i32::MAX));
+        }
+
+        self.buffer.clear();
+        for (x, y) in candidates {
+            let mut cell = self.cells.get(&(x, y)).cloned().unwrap_or_else(|| default.clone());
+
+            let neighbors: Vec<C> = (self.neighborhood)(x, y, i32::MAX, i32::MAX)
+                .into_iter()
+                .map(|(nx, ny)| self.cells.get(&(nx, ny)).cloned().unwrap_or_else(|| default.clone()))
+                .collect();
+            let neighbor_refs: Vec<&C> = neighbors.iter().collect();
+
+            (self.transition)(&mut cell, &neighbor_refs);
+
+            if cell != default {
+                self.buffer.insert((x, y), cell);
+            }
+        }
+
+        mem::swap(&mut self.cells, &mut self.buffer);
+    }
+
+    /// Iterate over every non-default cell and its coordinate.
+    pub fn cells(&self) -> impl Iterator<Item = (&Coord, &C)> {
+        self.cells.iter()
+    }
+}
+
+#[test]
+fn test_sparse_simulation_drops_cells_that_return_to_default() {
+    let mut sim = SparseSimulation::from_cells(
+        |cell: &mut i32, _neighbors: &[&i32]| *cell = 0,
+        crate::ca::von_neuman,
+        [(0, 0, 1)],
+    );
+    sim.step();
+    assert_eq!(sim.cells().count(), 0);
+}
+
+#[test]
+fn test_sparse_simulation_evaluates_the_frontier_of_occupied_cells_and_their_neighbors() {
+    let mut sim = SparseSimulation::from_cells(
+        |cell: &mut i32, _neighbors: &[&i32]| *cell = 1,
+        crate::ca::von_neuman,
+        [(0, 0, 1)],
+    );
+    sim.step();
+    // (0, 0) plus its 4 von Neumann neighbors all get evaluated and all turn non-default.
+    assert_eq!(sim.cells().count(), 5);
+}
+
+#[test]
+fn test_sparse_and_dense_backends_agree_on_the_same_rule() {
+    use crate::ca::{moore, Simulation};
+
+    fn life(cell: &mut u8, neighbors: &[&u8]) {
+        let alive = neighbors
+            .iter()
+            .filter(|n| {
+                let s: u8 = ***n;
+                s == 1
+            })
+            .count();
+        *cell = u8::from(alive == 3 || (*cell == 1 && alive == 2));
+    }
+
+    // Vertical blinker, centered away from the dense grid's edges so `Boundary::Void` and the
+    // sparse backend's unbounded neighborhood resolve identically.
+    let mut dense = Simulation::from_cells(5, 5, life, moore, vec![0u8; 25]);
+    dense.set_cell(2, 1, 1);
+    dense.set_cell(2, 2, 1);
+    dense.set_cell(2, 3, 1);
+    dense.step();
+
+    let mut sparse = SparseSimulation::from_cells(life, moore, [(2, 1, 1u8), (2, 2, 1), (2, 3, 1)]);
+    sparse.step();
+
+    let mut sparse_live: Vec<Coord> = sparse.cells().map(|(&c, _)| c).collect();
+    sparse_live.sort_unstable();
+
+    let mut dense_live: Vec<Coord> = (0..5)
+        .flat_map(|y| (0..5).map(move |x| (x, y)))
+        .filter(|&(x, y)| *dense.cell_at(x, y) == 1)
+        .collect();
+    dense_live.sort_unstable();
+
+    assert_eq!(sparse_live, dense_live);
+}